@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::store::StreamMessage;
+
+/// A strategy for encoding a typed payload into the opaque `data` bytes of a message, and decoding
+/// it back again.
+///
+/// The selected codec is stored alongside each message (as `StreamMessage::content_type`) so that
+/// a log containing a mix of formats can still be decoded on a per-message basis.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Codec {
+    /// JSON, via `serde_json`.
+    Json,
+    /// MessagePack, via `rmp_serde`.
+    MessagePack,
+    /// Opaque bytes with no structured (de)serialization.
+    Raw,
+}
+
+/// An error raised while encoding or decoding a typed payload.
+#[derive(Debug)]
+pub enum CodecError {
+    /// The value could not be serialized into the codec's wire format.
+    Encode(String),
+    /// The stored bytes could not be deserialized into the requested type.
+    Decode(String),
+    /// A typed read was attempted against a message stored under a different `message_type`.
+    TypeMismatch { expected: String, actual: String },
+    /// The `Raw` codec cannot encode or decode structured types.
+    UnsupportedForRaw,
+}
+
+impl Codec {
+    /// The IANA-style content type describing this codec's wire format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Codec::Json => "application/json",
+            Codec::MessagePack => "application/msgpack",
+            Codec::Raw => "application/octet-stream",
+        }
+    }
+
+    /// Serialize a value into this codec's wire format.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        match self {
+            Codec::Json => {
+                serde_json::to_vec(value).map_err(|e| CodecError::Encode(e.to_string()))
+            }
+            Codec::MessagePack => {
+                rmp_serde::to_vec(value).map_err(|e| CodecError::Encode(e.to_string()))
+            }
+            Codec::Raw => Err(CodecError::UnsupportedForRaw),
+        }
+    }
+
+    /// Deserialize a value of this codec's wire format.
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        match self {
+            Codec::Json => {
+                serde_json::from_slice(bytes).map_err(|e| CodecError::Decode(e.to_string()))
+            }
+            Codec::MessagePack => {
+                rmp_serde::from_slice(bytes).map_err(|e| CodecError::Decode(e.to_string()))
+            }
+            Codec::Raw => Err(CodecError::UnsupportedForRaw),
+        }
+    }
+}
+
+/// A type that can be written to and read from the store by name.
+///
+/// The associated [`Registered::TYPE_NAME`] is stored as a message's `message_type`, which lets a
+/// typed read detect when the stored message does not match the requested Rust type.
+pub trait Registered {
+    /// The stable `message_type` string under which values of this type are stored.
+    const TYPE_NAME: &'static str;
+}
+
+/// Associates each registered `message_type` with the codec used to (de)serialize it.
+///
+/// Types default to [`Codec::Json`] unless registered with an explicit codec.
+pub struct TypeRegistry {
+    codecs: HashMap<String, Codec>,
+    default_codec: Codec,
+}
+
+impl TypeRegistry {
+    pub fn new() -> Self {
+        Self {
+            codecs: HashMap::new(),
+            default_codec: Codec::Json,
+        }
+    }
+
+    /// Register a type along with the codec used to encode and decode it.
+    pub fn register<T: Registered>(&mut self, codec: Codec) -> &mut Self {
+        self.codecs.insert(T::TYPE_NAME.to_owned(), codec);
+        self
+    }
+
+    /// The codec associated with a `message_type`, falling back to the default codec.
+    pub fn codec_for(&self, type_name: &str) -> Codec {
+        self.codecs
+            .get(type_name)
+            .copied()
+            .unwrap_or(self.default_codec)
+    }
+}
+
+impl Default for TypeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decode a single already-read message into a typed value.
+///
+/// Returns [`CodecError::TypeMismatch`] when the stored `message_type` does not match the requested
+/// type, and decodes `data` using the codec recorded on the message so mixed-format logs round-trip
+/// correctly.
+pub fn read_typed<T: DeserializeOwned + Registered>(
+    message: &StreamMessage,
+) -> Result<T, CodecError> {
+    if message.message_type != T::TYPE_NAME {
+        return Err(CodecError::TypeMismatch {
+            expected: T::TYPE_NAME.to_owned(),
+            actual: message.message_type.clone(),
+        });
+    }
+    message.content_type.decode(&message.data)
+}
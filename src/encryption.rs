@@ -0,0 +1,85 @@
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// The size, in bytes, of the randomly generated nonce prefixed to each ciphertext.
+const NONCE_LEN: usize = 12;
+
+/// An error raised while decrypting a stored payload.
+#[derive(Eq, PartialEq, Debug)]
+pub enum EncryptionError {
+    /// The ciphertext was shorter than the nonce prefix and cannot be decrypted.
+    Malformed,
+    /// Authenticated decryption failed, indicating tampering or corruption.
+    Decryption,
+}
+
+/// Transparently encrypts message payloads at rest.
+///
+/// An implementation is given the name of the stream a payload belongs to so it can use a
+/// per-stream key, following the envelope-encryption approach used by object stores. Only the
+/// `data` of a message is encrypted; its `message_type` and position metadata stay in the clear so
+/// indices and concurrency control continue to work.
+pub trait EncryptionProvider: Send + Sync {
+    /// Encrypt `plaintext` for `stream`, returning the bytes to persist.
+    fn encrypt(&self, stream: &str, plaintext: &[u8]) -> Vec<u8>;
+
+    /// Decrypt a previously encrypted payload for `stream`.
+    fn decrypt(&self, stream: &str, ciphertext: &[u8]) -> Result<Vec<u8>, EncryptionError>;
+}
+
+/// A [`ChaCha20Poly1305`]-based provider that derives a per-stream key from a master key.
+///
+/// The per-stream key is `SHA-256(master_key || stream)`, and each payload is stored as a freshly
+/// generated nonce followed by the AEAD ciphertext. Because the nonce is part of the stored bytes,
+/// decryption recovers it without any additional bookkeeping.
+pub struct ChaCha20Poly1305Provider {
+    master_key: Vec<u8>,
+}
+
+impl ChaCha20Poly1305Provider {
+    /// Create a provider from a master key, from which per-stream keys are derived.
+    pub fn new(master_key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            master_key: master_key.into(),
+        }
+    }
+
+    fn cipher_for(&self, stream: &str) -> ChaCha20Poly1305 {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.master_key);
+        hasher.update(stream.as_bytes());
+        let key = hasher.finalize();
+        ChaCha20Poly1305::new(Key::from_slice(&key))
+    }
+}
+
+impl EncryptionProvider for ChaCha20Poly1305Provider {
+    fn encrypt(&self, stream: &str, plaintext: &[u8]) -> Vec<u8> {
+        let cipher = self.cipher_for(stream);
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(nonce, plaintext)
+            .expect("ChaCha20Poly1305 encryption is infallible for in-memory payloads");
+
+        let mut stored = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        stored.extend_from_slice(&nonce_bytes);
+        stored.extend_from_slice(&ciphertext);
+        stored
+    }
+
+    fn decrypt(&self, stream: &str, ciphertext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        if ciphertext.len() < NONCE_LEN {
+            return Err(EncryptionError::Malformed);
+        }
+        let (nonce_bytes, payload) = ciphertext.split_at(NONCE_LEN);
+        let cipher = self.cipher_for(stream);
+        cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), payload)
+            .map_err(|_| EncryptionError::Decryption)
+    }
+}
@@ -0,0 +1,161 @@
+use std::io;
+use std::path::Path;
+
+use crate::memory_stream_store::storage::FileBackend;
+use crate::store::{
+    Message, ReadDirection, ReadError, ReadFromCategory, ReadFromStream, Stream, StreamVersion,
+    WriteResult, WriteToStream,
+};
+use crate::MemoryStreamStore;
+
+/// A durable, append-only implementation of a stream store.
+///
+/// This is a thin convenience wrapper that pairs a [`MemoryStreamStore`] with a [`FileBackend`]:
+/// the store keeps the log, indices and revision map in memory, while the backend makes every write
+/// durable and replays the log on startup. It exists so callers that only want "a file-backed
+/// store" do not have to assemble the pieces themselves, while the framing and replay logic lives
+/// in a single place rather than being duplicated here.
+pub struct FileStreamStore {
+    inner: MemoryStreamStore,
+}
+
+impl FileStreamStore {
+    /// Open (creating if necessary) the log file at `path`, replaying it to rebuild the indices.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let inner = MemoryStreamStore::with_backend(Box::new(FileBackend::open(path)?))?;
+        Ok(Self { inner })
+    }
+
+    /// Toggle per-message checksum verification on read; on by default for this durable store.
+    pub fn with_checksum_verification(mut self, verify: bool) -> Self {
+        self.inner = self.inner.with_checksum_verification(verify);
+        self
+    }
+}
+
+impl ReadFromStream for FileStreamStore {
+    fn read_from_stream(
+        &self,
+        stream_name: &str,
+        direction: ReadDirection,
+    ) -> Result<(StreamVersion, Stream), ReadError> {
+        self.inner.read_from_stream(stream_name, direction)
+    }
+}
+
+impl ReadFromCategory for FileStreamStore {
+    fn read_from_category(
+        &mut self,
+        category_name: &str,
+        offset: usize,
+        max_messages: Option<usize>,
+    ) -> Result<Stream, ReadError> {
+        self.inner
+            .read_from_category(category_name, offset, max_messages)
+    }
+}
+
+impl WriteToStream for FileStreamStore {
+    fn write_to_stream(
+        &mut self,
+        stream_name: &str,
+        expected_version: StreamVersion,
+        messages: &[Message],
+    ) -> WriteResult {
+        self.inner
+            .write_to_stream(stream_name, expected_version, messages)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileStreamStore;
+    use crate::store::{
+        Message, ReadDirection, ReadFromCategory, ReadFromStream, StreamVersion, WriteResult,
+        WriteToStream,
+    };
+
+    struct TempPath {
+        path: std::path::PathBuf,
+    }
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "rusty_stream_{}_{}.log",
+                std::process::id(),
+                name
+            ));
+            let _ = std::fs::remove_file(&path);
+            Self { path }
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    fn message(message_type: &str) -> Message {
+        Message {
+            message_type: message_type.to_owned(),
+            data: r#"{"test": "data"}"#.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn it_persists_and_reads_back_after_reopen() {
+        let temp = TempPath::new("persist");
+
+        {
+            let mut store = FileStreamStore::open(&temp.path).unwrap();
+            let result =
+                store.write_to_stream("TestStream-1", StreamVersion::NoStream, &[message("First")]);
+            assert_eq!(result, WriteResult::Ok(crate::store::MessagePosition { revision: 0, position: 0 }));
+            store.write_to_stream("TestStream-1", StreamVersion::Revision(0), &[message("Second")]);
+        }
+
+        // Re-open the same file: the replay must rebuild the stream, its revision and the category.
+        let mut store = FileStreamStore::open(&temp.path).unwrap();
+        let (version, messages) = store
+            .read_from_stream("TestStream-1", ReadDirection::Forwards)
+            .unwrap();
+        assert_eq!(version, StreamVersion::Revision(1));
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].message_type, "First");
+        assert_eq!(messages[1].message_type, "Second");
+
+        let category = store.read_from_category("TestStream", 0, None).unwrap();
+        assert_eq!(category.len(), 2);
+
+        // Optimistic concurrency still fires after recovery.
+        let conflict =
+            store.write_to_stream("TestStream-1", StreamVersion::NoStream, &[message("Third")]);
+        assert_eq!(conflict, WriteResult::WrongExpectedVersion);
+    }
+
+    #[test]
+    fn it_discards_a_truncated_trailing_record() {
+        use std::io::Write;
+
+        let temp = TempPath::new("truncated");
+        {
+            let mut store = FileStreamStore::open(&temp.path).unwrap();
+            store.write_to_stream("TestStream-1", StreamVersion::NoStream, &[message("First")]);
+        }
+
+        // Simulate a crash mid-write: a length prefix promising more bytes than were flushed.
+        let mut file = std::fs::OpenOptions::new().append(true).open(&temp.path).unwrap();
+        file.write_all(&1024u32.to_le_bytes()).unwrap();
+        file.write_all(&[0u8; 8]).unwrap();
+        drop(file);
+
+        let (version, messages) = FileStreamStore::open(&temp.path)
+            .unwrap()
+            .read_from_stream("TestStream-1", ReadDirection::Forwards)
+            .unwrap();
+        assert_eq!(version, StreamVersion::Revision(0));
+        assert_eq!(messages.len(), 1);
+    }
+}
@@ -1,5 +1,8 @@
+pub mod codec;
+pub mod encryption;
+pub mod file_stream_store;
 pub mod memory_stream_store;
-pub mod stream;
+pub mod store;
 
 pub use crate::memory_stream_store::store::MemoryStreamStore;
-pub use crate::stream::*;
+pub use crate::store::*;
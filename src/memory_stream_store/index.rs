@@ -9,6 +9,12 @@ pub struct LogPositionIndex {
     idx: HashMap<String, Vec<usize>>,
 }
 
+impl Default for LogPositionIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LogPositionIndex {
     pub fn new() -> Self {
         LogPositionIndex {
@@ -0,0 +1,120 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::StreamMessage;
+
+/// A single persisted record: the stream a message was written to, paired with the message.
+///
+/// The stream name is carried alongside the `StreamMessage` because it cannot be recovered from the
+/// message alone, yet it is required to re-run the `split('-')` category inference and rebuild the
+/// stream/category indices and revision map during [`StorageBackend::replay`].
+#[derive(Serialize, Deserialize)]
+pub struct StoredRecord {
+    pub stream_name: String,
+    pub message: StreamMessage,
+}
+
+/// A pluggable physical storage engine sitting behind the abstract stream store.
+///
+/// The store owns the in-memory log, indices and revision map; a `StorageBackend` is responsible
+/// only for making a batch of writes durable and for streaming them back, in global-position order,
+/// so the store can rebuild its in-memory state on startup.
+pub trait StorageBackend: Send {
+    /// Durably persist a batch of records, returning only once the batch is committed.
+    fn append(&mut self, records: &[StoredRecord]) -> io::Result<()>;
+
+    /// Stream every persisted record back in global-position order.
+    fn replay(&mut self) -> io::Result<Vec<StoredRecord>>;
+}
+
+/// A backend that persists nothing; used by the pure in-memory store.
+pub struct NullBackend;
+
+impl StorageBackend for NullBackend {
+    fn append(&mut self, _records: &[StoredRecord]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn replay(&mut self) -> io::Result<Vec<StoredRecord>> {
+        Ok(Vec::new())
+    }
+}
+
+/// A crash-safe, file-based backend.
+///
+/// Each record is framed as a little-endian `u32` length prefix followed by its serialized bytes,
+/// and the file is fsync'd after every [`StorageBackend::append`] so a commit is durable before the
+/// store reports success. On replay, a record whose length prefix promises more bytes than remain
+/// (a write interrupted by a crash) is detected and discarded, so the log recovers to the last
+/// fully-written message rather than panicking.
+pub struct FileBackend {
+    file: File,
+}
+
+impl FileBackend {
+    /// Open (creating if necessary) the append-only log file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self { file })
+    }
+
+    /// Read the next length-prefixed record, returning `Ok(None)` at a clean end of file or when a
+    /// truncated/partial record is encountered at the tail.
+    fn read_record(reader: &mut impl Read) -> io::Result<Option<StoredRecord>> {
+        let mut len_buf = [0u8; 4];
+        match reader.read_exact(&mut len_buf) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        let len = u32::from_le_bytes(len_buf) as usize;
+        let mut payload = vec![0u8; len];
+        match reader.read_exact(&mut payload) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        match bincode::deserialize(&payload) {
+            Ok(record) => Ok(Some(record)),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl StorageBackend for FileBackend {
+    fn append(&mut self, records: &[StoredRecord]) -> io::Result<()> {
+        for record in records {
+            let payload = bincode::serialize(record)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            self.file.write_all(&(payload.len() as u32).to_le_bytes())?;
+            self.file.write_all(&payload)?;
+        }
+        self.file.sync_all()?;
+        Ok(())
+    }
+
+    fn replay(&mut self) -> io::Result<Vec<StoredRecord>> {
+        self.file.seek(SeekFrom::Start(0))?;
+        let mut reader = BufReader::new(&mut self.file);
+        let mut records = Vec::new();
+        let mut recovered_len = 0;
+        while let Some(record) = Self::read_record(&mut reader)? {
+            records.push(record);
+            recovered_len = reader.stream_position()?;
+        }
+        // Cut off any partial record at the tail and leave the handle positioned at the end, so
+        // subsequent appends land directly after the recovered log.
+        self.file.set_len(recovered_len)?;
+        self.file.seek(SeekFrom::End(0))?;
+        Ok(records)
+    }
+}
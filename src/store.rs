@@ -7,7 +7,7 @@ pub struct Message {
     pub data: Vec<u8>,
 }
 
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MessagePosition {
     /// The global position of a message in the store. The first position should have an index of 0.
     pub position: usize,
@@ -16,20 +16,36 @@ pub struct MessagePosition {
 }
 
 /// A message that is read from a stream
-#[derive(Clone)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct StreamMessage {
     /// A unique identifier for a message.
     pub id: String,
+    /// The name of the stream the message was written to.
+    pub stream_name: String,
     /// The type of a message.
     pub message_type: String,
     /// The data of a message.
     pub data: Vec<u8>,
+    /// The codec that was used to encode `data`, so mixed-format logs decode correctly.
+    pub content_type: crate::codec::Codec,
+    /// A CRC32C of `message_type` concatenated with the stored `data`, computed at append time and
+    /// verified on read to catch silent corruption between write and read.
+    pub checksum: u32,
     /// The positions (position and revision) of a message in the store and in its stream.
     pub position: MessagePosition,
 }
 
+/// Compute the CRC32C stored on a [`StreamMessage`]: the checksum of its `message_type` bytes
+/// followed by its (possibly encrypted) `data`.
+pub(crate) fn checksum_of(message_type: &str, data: &[u8]) -> u32 {
+    let mut bytes = Vec::with_capacity(message_type.len() + data.len());
+    bytes.extend_from_slice(message_type.as_bytes());
+    bytes.extend_from_slice(data);
+    crc32c::crc32c(&bytes)
+}
+
 /// The version of a stream
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
 pub enum StreamVersion {
     /// The stream does not exist.
     NoStream,
@@ -45,6 +61,21 @@ pub enum WriteResult {
     Ok(MessagePosition),
     /// The write was unsuccessful because of an expected version mismatch.
     WrongExpectedVersion,
+    /// A batched write was rejected because one of its streams had an unexpected version. The
+    /// named stream is the one whose `expected_version` did not match, and nothing in the batch
+    /// was written.
+    WrongExpectedVersionForStream(String),
+}
+
+/// An opaque continuation token for paginated category reads.
+///
+/// A cursor marks the global position a read should resume from; it is obtained from a previous
+/// page and passed back to fetch the next one. A returned cursor of `None` means the category has
+/// been fully drained.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Cursor {
+    /// The next global position to resume from.
+    pub(crate) position: usize,
 }
 
 /// The direction in which to read a stream.
@@ -57,6 +88,16 @@ pub enum ReadDirection {
 /// A stream of messages, represented as a vector.
 pub type Stream = Vec<StreamMessage>;
 
+/// An error raised while materializing messages for a read.
+#[derive(Eq, PartialEq, Debug)]
+pub enum ReadError {
+    /// A stored message could not be decrypted, which indicates tampering or corruption.
+    Decryption(String),
+    /// A stored message's checksum did not match its bytes, which indicates silent corruption. The
+    /// payload carries the id of the offending message.
+    Corruption(String),
+}
+
 /// A trait that expresses the behaviour of reading from a stream
 pub trait ReadFromStream {
     /// Read a given stream in its entirety.
@@ -68,7 +109,7 @@ pub trait ReadFromStream {
         &self,
         stream_name: &str,
         read_direction: ReadDirection,
-    ) -> (StreamVersion, Stream);
+    ) -> Result<(StreamVersion, Stream), ReadError>;
 }
 
 /// A trait that expresses the behaviour of writing to a stream
@@ -78,7 +119,7 @@ pub trait WriteToStream {
     /// # Arguments
     /// * `stream_name` - The stream to write to.
     /// * `expected_version` - The expected version of the stream at write time, used for Optimistic.
-    /// Concurrency Control.
+    ///   Concurrency Control.
     /// * `messages` - A slice of messages to write into the stream.
     fn write_to_stream(
         &mut self,
@@ -102,14 +143,33 @@ pub trait ReadFromCategory {
     /// # Arguments
     /// * `category_name` - The name of the category to read.
     /// * `offset` - The offset at which to start reading. This refers to the global position of
-    /// the store.
+    ///   the store.
     /// * `max_messages` - The maximum number of messages to read. If None, all messages for the
-    /// category will be returned.
+    ///   category will be returned.
     ///
     fn read_from_category(
         &mut self,
         category_name: &str,
         offset: usize,
         max_messages: Option<usize>,
-    ) -> Stream;
+    ) -> Result<Stream, ReadError>;
+}
+
+/// A trait that expresses the behaviour of following a category as new messages are written.
+///
+/// Unlike [`ReadFromCategory`], which performs a single one-shot read, a subscription first drains
+/// all existing messages in a category at or after a given offset and then transparently tails
+/// newly appended messages as they are written, with no gap and no duplicate at the handoff.
+pub trait SubscribeToCategory {
+    /// The subscription handle returned by [`SubscribeToCategory::subscribe_to_category`].
+    type Subscription;
+
+    /// Start following a category, catching up from `from_offset` before tailing live writes.
+    ///
+    /// # Arguments
+    /// * `category_name` - The name of the category to follow.
+    /// * `from_offset` - The global position to start from. Messages at or after this position
+    ///   will be delivered by the subscription, in global order.
+    fn subscribe_to_category(&self, category_name: &str, from_offset: usize)
+        -> Self::Subscription;
 }